@@ -1,37 +1,568 @@
 use std::fmt;
+use std::fs::File;
+use std::io;
+use std::path::PathBuf;
 
-struct Album {
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct ArtistId(pub String);
+
+impl fmt::Display for ArtistId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct AlbumId(pub String);
+
+impl fmt::Display for AlbumId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A canonical MusicBrainz reference (a URL or bare UUID) giving an entry a
+/// stable external identity that later enrichment or deduplication can key on.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct MbRef(pub String);
+
+impl fmt::Display for MbRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A release date with an optional month and day. A year-only date sorts
+/// before any fully-dated release in the same year because `None` orders
+/// before `Some` under the derived `Ord`.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct AlbumDate {
+    pub year: u32,
+    pub month: Option<u32>,
+    pub day: Option<u32>,
+}
+
+impl fmt::Display for AlbumDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.year)?;
+        if let Some(month) = self.month {
+            write!(f, "-{:02}", month)?;
+            if let Some(day) = self.day {
+                write!(f, "-{:02}", day)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A manual tiebreaker used to force an order when two releases are otherwise
+/// indistinguishable. An unset sequence (`None`) sorts before any set one.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct AlbumSeq(pub Option<u32>);
+
+/// A single track on an album. `artist` is only populated when the track's
+/// credits differ from the album artist (guest or featured performers);
+/// otherwise it stays empty and the track inherits the album's artist.
+#[derive(Clone, Serialize, Deserialize)]
+struct Track {
+    pub number: u32,
     pub title: String,
-    pub artist: String,
+    pub artist: Vec<String>,
+}
+
+impl fmt::Display for Track {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}. {}", self.number, self.title)?;
+        if !self.artist.is_empty() {
+            write!(f, " ({})", self.artist.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Album {
+    pub id: AlbumId,
+    pub date: AlbumDate,
+    pub seq: AlbumSeq,
+    pub tracks: Vec<Track>,
+    pub musicbrainz: Option<MbRef>,
+}
+
+impl Album {
+    /// The tuple ordering albums within an artist: chronological first, then
+    /// the manual sequence, then the title as a final deterministic fallback.
+    fn get_sort_key(&self) -> (&AlbumDate, &AlbumSeq, &AlbumId) {
+        (&self.date, &self.seq, &self.id)
+    }
+
+    fn set_seq(&mut self, seq: u32) {
+        self.seq = AlbumSeq(Some(seq));
+    }
+
+    // Not exercised in `main`; covered by a unit test instead.
+    #[allow(dead_code)]
+    fn clear_seq(&mut self) {
+        self.seq = AlbumSeq(None);
+    }
+
+    fn set_musicbrainz_ref(&mut self, reference: impl Into<String>) {
+        self.musicbrainz = Some(MbRef(reference.into()));
+    }
+
+    // Not exercised in `main`; covered by a unit test instead.
+    #[allow(dead_code)]
+    fn clear_musicbrainz_ref(&mut self) {
+        self.musicbrainz = None;
+    }
+}
+
+/// Equality follows the sort key, not the full struct: two albums with the
+/// same date, sequence, and id compare equal even if their tracks or
+/// `musicbrainz` ref differ. This is an ordering-only `Eq`, useful for the
+/// `Vec::dedup`-style comparisons `Merge` relies on, not for content
+/// comparison; callers who need that should compare fields (or serialized
+/// form) directly.
+impl PartialEq for Album {
+    fn eq(&self, other: &Self) -> bool {
+        self.get_sort_key() == other.get_sort_key()
+    }
+}
+
+impl Eq for Album {}
+
+impl PartialOrd for Album {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Album {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.get_sort_key().cmp(&other.get_sort_key())
+    }
 }
 
 impl fmt::Display for Album {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} ({})", self.title, self.artist)
+        write!(f, "{} ({})", self.id, self.date)?;
+        if let Some(reference) = self.musicbrainz.as_ref().filter(|_| f.alternate()) {
+            write!(f, " <{}>", reference)?;
+        }
+        self.tracks
+            .iter()
+            .try_fold((), |_, track| write!(f, "\n        {}", track))
     }
 }
 
-struct Albums(pub Vec<Album>);
+#[derive(Clone, Serialize, Deserialize)]
+struct Artist {
+    pub id: ArtistId,
+    pub sort_name: Option<String>,
+    pub albums: Vec<Album>,
+    pub musicbrainz: Option<MbRef>,
+}
+
+impl Artist {
+    /// The name to order this artist by: the explicit `sort_name` when set
+    /// (e.g. "Beatles, The"), otherwise the display name. This keeps leading
+    /// articles and "surname, forename" orderings out of the printed name.
+    fn get_sort_key(&self) -> &str {
+        self.sort_name.as_deref().unwrap_or(&self.id.0)
+    }
+
+    fn set_sort_name(&mut self, sort_name: impl Into<String>) {
+        self.sort_name = Some(sort_name.into());
+    }
+
+    // Not exercised in `main` (there's no realistic edit that clears a sort
+    // name back to "unset" in the merge demo); covered by a unit test instead.
+    #[allow(dead_code)]
+    fn clear_sort_name(&mut self) {
+        self.sort_name = None;
+    }
+
+    fn set_musicbrainz_ref(&mut self, reference: impl Into<String>) {
+        self.musicbrainz = Some(MbRef(reference.into()));
+    }
+
+    // Not exercised in `main`; covered by a unit test instead.
+    #[allow(dead_code)]
+    fn clear_musicbrainz_ref(&mut self) {
+        self.musicbrainz = None;
+    }
+}
+
+impl PartialEq for Artist {
+    fn eq(&self, other: &Self) -> bool {
+        self.get_sort_key() == other.get_sort_key()
+    }
+}
+
+impl Eq for Artist {}
+
+impl PartialOrd for Artist {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Artist {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.get_sort_key().cmp(other.get_sort_key())
+    }
+}
+
+impl fmt::Display for Artist {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.id)?;
+        if let Some(reference) = self.musicbrainz.as_ref().filter(|_| f.alternate()) {
+            write!(f, " <{}>", reference)?;
+        }
+        writeln!(f)?;
+        let mut albums = self.albums.clone();
+        albums.sort();
+        albums.iter().try_fold((), |_, album| {
+            if f.alternate() {
+                writeln!(f, "    {:#}", album)
+            } else {
+                writeln!(f, "    {}", album)
+            }
+        })
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Albums(pub Vec<Artist>);
 
 impl fmt::Display for Albums {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.0.iter().fold(Ok(()), |result, album| {
-            result.and_then(|_| writeln!(f, "{}", album))
+        let mut artists = self.0.clone();
+        artists.sort();
+        artists.iter().try_fold((), |_, artist| {
+            if f.alternate() {
+                write!(f, "{:#}", artist)
+            } else {
+                write!(f, "{}", artist)
+            }
         })
     }
 }
 
+/// Folds another value of the same type into this one, unioning their
+/// contents rather than replacing them. Merging is idempotent: merging a
+/// value into itself leaves it unchanged, and existing metadata is never
+/// overwritten by the incoming side — only gaps are filled.
+trait Merge {
+    fn merge_in_place(&mut self, other: Self);
+}
+
+impl Merge for Album {
+    fn merge_in_place(&mut self, other: Self) {
+        if self.date.month.is_none() {
+            self.date.month = other.date.month;
+        }
+        if self.date.day.is_none() {
+            self.date.day = other.date.day;
+        }
+        if self.seq.0.is_none() {
+            self.seq = other.seq;
+        }
+        if self.musicbrainz.is_none() {
+            self.musicbrainz = other.musicbrainz;
+        }
+        for track in other.tracks {
+            if !self.tracks.iter().any(|t| t.number == track.number) {
+                self.tracks.push(track);
+            }
+        }
+        self.tracks.sort_by_key(|t| t.number);
+    }
+}
+
+impl Merge for Artist {
+    fn merge_in_place(&mut self, other: Self) {
+        if self.sort_name.is_none() {
+            self.sort_name = other.sort_name;
+        }
+        if self.musicbrainz.is_none() {
+            self.musicbrainz = other.musicbrainz;
+        }
+        for album in other.albums {
+            match self.albums.iter_mut().find(|a| a.id == album.id) {
+                Some(existing) => existing.merge_in_place(album),
+                None => self.albums.push(album),
+            }
+        }
+        self.albums.sort();
+    }
+}
+
+impl Merge for Albums {
+    fn merge_in_place(&mut self, other: Self) {
+        for artist in other.0 {
+            match self.0.iter_mut().find(|a| a.id == artist.id) {
+                Some(existing) => existing.merge_in_place(artist),
+                None => self.0.push(artist),
+            }
+        }
+        // An artist untouched by `other` never passes through
+        // `Artist::merge_in_place`, so its albums need sorting here too —
+        // otherwise the "result stays sorted" invariant only held for
+        // artists the incoming side happened to mention.
+        for artist in &mut self.0 {
+            artist.albums.sort();
+        }
+        self.0.sort();
+    }
+}
+
+/// A persistence backend for an `Albums` collection. Reading and writing are
+/// kept behind a trait so the example can grow other backends later without
+/// the rest of the program caring where the library actually lives.
+trait Database {
+    fn read(&self) -> io::Result<Albums>;
+    fn write(&self, albums: &Albums) -> io::Result<()>;
+}
+
+/// A [`Database`] that stores the whole collection as a JSON file on disk.
+struct JsonDatabase {
+    pub path: PathBuf,
+}
+
+impl JsonDatabase {
+    fn new(path: impl Into<PathBuf>) -> Self {
+        JsonDatabase { path: path.into() }
+    }
+}
+
+impl Database for JsonDatabase {
+    fn read(&self) -> io::Result<Albums> {
+        let file = File::open(&self.path)?;
+        serde_json::from_reader(file).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn write(&self, albums: &Albums) -> io::Result<()> {
+        let file = File::create(&self.path)?;
+        serde_json::to_writer_pretty(file, albums)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
 fn main() {
     let albums = Albums(vec![
-        Album {
-            title: "Sgt. Pepper's Lonely Hearts Club Band".into(),
-            artist: "The Beatles".into(),
+        Artist {
+            id: ArtistId("The Beatles".into()),
+            sort_name: Some("Beatles, The".into()),
+            albums: vec![
+                Album {
+                    id: AlbumId("Sgt. Pepper's Lonely Hearts Club Band".into()),
+                    date: AlbumDate {
+                        year: 1967,
+                        month: Some(5),
+                        day: Some(26),
+                    },
+                    seq: AlbumSeq(None),
+                    tracks: vec![Track {
+                        number: 1,
+                        title: "Sgt. Pepper's Lonely Hearts Club Band".into(),
+                        artist: vec![],
+                    }],
+                    musicbrainz: Some(MbRef(
+                        "https://musicbrainz.org/release-group/\
+                         4c4ab1c0-8b8e-3b6d-9f3a-9f3a9f3a9f3a"
+                            .into(),
+                    )),
+                },
+                Album {
+                    id: AlbumId("Revolver".into()),
+                    date: AlbumDate {
+                        year: 1966,
+                        month: None,
+                        day: None,
+                    },
+                    seq: AlbumSeq(None),
+                    tracks: vec![],
+                    musicbrainz: None,
+                },
+            ],
+            musicbrainz: Some(MbRef(
+                "https://musicbrainz.org/artist/\
+                 b10bbbfc-cf9e-42e0-be17-e2c3e1d2600d"
+                    .into(),
+            )),
         },
-        Album {
-            title: "Dark Side of the Moon".into(),
-            artist: "Pink Floyd".into(),
+        Artist {
+            id: ArtistId("Pink Floyd".into()),
+            sort_name: None,
+            albums: vec![Album {
+                id: AlbumId("Dark Side of the Moon".into()),
+                date: AlbumDate {
+                    year: 1973,
+                    month: Some(3),
+                    day: Some(1),
+                },
+                seq: AlbumSeq(None),
+                tracks: vec![Track {
+                    number: 1,
+                    title: "Speak to Me".into(),
+                    artist: vec![],
+                }],
+                musicbrainz: None,
+            }],
+            musicbrainz: None,
         },
     ]);
 
+    let database = JsonDatabase::new("albums.json");
+    database.write(&albums).expect("failed to write library");
+
+    let mut albums = database.read().expect("failed to read library");
+
+    // An imported update carrying extra metadata to reconcile into the
+    // library. The `set_*` helpers fill fields in after construction.
+    let mut incoming = Artist {
+        id: ArtistId("Pink Floyd".into()),
+        sort_name: None,
+        albums: vec![Album {
+            id: AlbumId("Dark Side of the Moon".into()),
+            date: AlbumDate {
+                year: 1973,
+                month: None,
+                day: None,
+            },
+            seq: AlbumSeq(None),
+            tracks: vec![],
+            musicbrainz: None,
+        }],
+        musicbrainz: None,
+    };
+    incoming.set_sort_name("Floyd, Pink");
+    incoming.set_musicbrainz_ref("https://musicbrainz.org/artist/83d91898-7763-47d7-b03b-b92132375c47");
+    incoming.albums[0].set_seq(1);
+    incoming.albums[0]
+        .set_musicbrainz_ref("https://musicbrainz.org/release-group/f5093c06-23e3-404f-aeaa-40f72885ee3a");
+
+    albums.merge_in_place(Albums(vec![incoming]));
+
+    // Merging is idempotent: folding the library into itself changes nothing.
+    // Compare serialized form rather than `Display`, which doesn't render
+    // `seq`, so a merge that altered it wouldn't be caught here.
+    let merged = serde_json::to_string(&albums).expect("failed to serialize library");
+    albums.merge_in_place(albums.clone());
+    assert_eq!(
+        merged,
+        serde_json::to_string(&albums).expect("failed to serialize library")
+    );
+
     println!("{}", albums);
+    println!("{:#}", albums);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_round_trip_reproduces_collection() {
+        let original = Albums(vec![Artist {
+            id: ArtistId("The Beatles".into()),
+            sort_name: Some("Beatles, The".into()),
+            albums: vec![Album {
+                id: AlbumId("Revolver".into()),
+                date: AlbumDate {
+                    year: 1966,
+                    month: Some(8),
+                    day: Some(5),
+                },
+                seq: AlbumSeq(Some(1)),
+                tracks: vec![Track {
+                    number: 1,
+                    title: "Taxman".into(),
+                    artist: vec![],
+                }],
+                musicbrainz: Some(MbRef(
+                    "https://musicbrainz.org/release-group/\
+                     72d15666-99a7-321e-b1f3-a3f8c09dff9f"
+                        .into(),
+                )),
+            }],
+            musicbrainz: None,
+        }]);
+
+        let path = std::env::temp_dir().join("impl-display-for-vec-round-trip.json");
+        let database = JsonDatabase::new(&path);
+        database.write(&original).expect("failed to write library");
+        let loaded = database.read().expect("failed to read library");
+
+        // `Albums` orders equality by sort key only, so compare the full
+        // serialized form to prove every field survived the round trip.
+        assert_eq!(
+            serde_json::to_string(&original).unwrap(),
+            serde_json::to_string(&loaded).unwrap(),
+        );
+    }
+
+    #[test]
+    fn clear_sort_name_falls_back_to_display_name() {
+        let mut artist = Artist {
+            id: ArtistId("The Beatles".into()),
+            sort_name: Some("Beatles, The".into()),
+            albums: vec![],
+            musicbrainz: None,
+        };
+        assert_eq!(artist.get_sort_key(), "Beatles, The");
+
+        artist.clear_sort_name();
+        assert_eq!(artist.get_sort_key(), "The Beatles");
+    }
+
+    #[test]
+    fn clear_musicbrainz_ref_unsets_artist_and_album() {
+        let mut artist = Artist {
+            id: ArtistId("Pink Floyd".into()),
+            sort_name: None,
+            albums: vec![Album {
+                id: AlbumId("The Wall".into()),
+                date: AlbumDate {
+                    year: 1979,
+                    month: None,
+                    day: None,
+                },
+                seq: AlbumSeq(None),
+                tracks: vec![],
+                musicbrainz: Some(MbRef("https://musicbrainz.org/release-group/stub".into())),
+            }],
+            musicbrainz: Some(MbRef("https://musicbrainz.org/artist/stub".into())),
+        };
+
+        artist.clear_musicbrainz_ref();
+        artist.albums[0].clear_musicbrainz_ref();
+
+        assert!(artist.musicbrainz.is_none());
+        assert!(artist.albums[0].musicbrainz.is_none());
+    }
+
+    #[test]
+    fn set_and_clear_seq_round_trip() {
+        let mut album = Album {
+            id: AlbumId("The Wall".into()),
+            date: AlbumDate {
+                year: 1979,
+                month: None,
+                day: None,
+            },
+            seq: AlbumSeq(None),
+            tracks: vec![],
+            musicbrainz: None,
+        };
+
+        album.set_seq(1);
+        assert_eq!(album.seq.0, Some(1));
+
+        album.clear_seq();
+        assert_eq!(album.seq.0, None);
+    }
 }